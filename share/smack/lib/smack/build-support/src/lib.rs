@@ -0,0 +1,81 @@
+//! Target-triple parsing shared by `build.rs` and its tests.
+//!
+//! `build.rs` is compiled and run as a standalone build-script binary, so a
+//! `#[cfg(test)]` module inside it never runs under `cargo test`. Keeping
+//! `Triple` here instead, in an ordinary lib crate, gives it a real test target.
+
+use std::env;
+
+/// A parsed `TARGET` triple, broken into the pieces the build script cares about.
+pub struct Triple {
+    arch: String,
+    os: String,
+}
+
+impl Triple {
+    /// Parses a triple like `x86_64-unknown-linux-gnu` into its arch/vendor/os segments.
+    /// We only keep `arch` and `os`; the vendor segment isn't used by anything here.
+    pub fn parse(target: &str) -> Triple {
+        let mut parts = target.split('-');
+        let arch = parts.next().unwrap_or("x86_64").to_string();
+        // vendor (e.g. "unknown", "apple") is skipped; os is the segment after it.
+        let os = parts.nth(1).unwrap_or("linux").to_string();
+        Triple { arch, os }
+    }
+
+    pub fn arch(&self) -> &str {
+        &self.arch
+    }
+
+    pub fn os(&self) -> &str {
+        &self.os
+    }
+
+    pub fn ptr_width(&self) -> &'static str {
+        // Match on prefix, not exact arch string: Rust triples append ABI/variant
+        // suffixes to the base 64-bit arch name (`riscv64gc`, `powerpc64le`, `mips64el`, ...).
+        let is_64_bit = self.arch == "x86_64"
+            || self.arch.starts_with("aarch64")
+            || self.arch.starts_with("riscv64")
+            || self.arch.starts_with("powerpc64")
+            || self.arch.starts_with("mips64")
+            || self.arch.starts_with("sparc64");
+        if is_64_bit {
+            "64"
+        } else {
+            "32"
+        }
+    }
+
+    /// Link-search directory suffix, mirroring SMACK's own `bin/linux64` / `bin/linux32` layout.
+    pub fn link_dir(&self) -> String {
+        format!("{}{}", self.os, self.ptr_width())
+    }
+
+    /// The C++ standard-library link name LLVM/Clang support libraries need on this OS,
+    /// overridable via `SMACK_CXX_STDLIB` for unusual toolchains.
+    pub fn cxx_stdlib(&self) -> String {
+        if let Ok(stdlib) = env::var("SMACK_CXX_STDLIB") {
+            return stdlib;
+        }
+        match self.os.as_str() {
+            "freebsd" | "openbsd" | "netbsd" | "dragonfly" => "c++".to_string(),
+            "darwin" | "ios" => "c++".to_string(),
+            "emscripten" => "estdc++".to_string(),
+            _ => "stdc++".to_string(),
+        }
+    }
+
+    /// The archiver/compiler driver override needed on this OS, or `None` to leave
+    /// `cc::Build` to auto-detect (its default is correct for GNU-style platforms
+    /// as well as MSVC, so we only override for the BSDs and Darwin, which prefer
+    /// `clang`/`llvm-ar`).
+    pub fn cxx_driver(&self) -> Option<(&'static str, &'static str)> {
+        match self.os.as_str() {
+            "freebsd" | "openbsd" | "netbsd" | "dragonfly" | "darwin" | "ios" => {
+                Some(("clang", "llvm-ar"))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use build_support::Triple;
+
+#[test]
+fn ptr_width_recognizes_64_bit_triples() {
+    let triples = [
+        "x86_64-unknown-linux-gnu",
+        "aarch64-unknown-linux-gnu",
+        "riscv64gc-unknown-linux-gnu",
+        "powerpc64le-unknown-linux-gnu",
+        "mips64el-unknown-linux-gnuabi64",
+    ];
+    for triple in triples {
+        assert_eq!(Triple::parse(triple).ptr_width(), "64", "{}", triple);
+    }
+}
+
+#[test]
+fn ptr_width_recognizes_32_bit_triples() {
+    let triples = ["i686-unknown-linux-gnu", "armv7-unknown-linux-gnueabihf"];
+    for triple in triples {
+        assert_eq!(Triple::parse(triple).ptr_width(), "32", "{}", triple);
+    }
+}
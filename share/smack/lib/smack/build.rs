@@ -1,11 +1,135 @@
 // build.rs
 
-fn main() {
-    cc::Build::new()
-        .file("src/smack-rust.c")
-        .define("CARGO_BUILD", None)
-        .include("src")
-        .compile("libsmack.a");
-    println!("cargo:rerun-if-changed=src/smack-rust.c");
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use build_support::Triple;
+
+/// Walks `dir` recursively (stack-based, no recursion) and returns every `.c`/`.h`
+/// file found under it.
+fn find_shim_sources(dir: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path.extension().and_then(|e| e.to_str()) == Some("c")
+                || path.extension().and_then(|e| e.to_str()) == Some("h")
+            {
+                found.push(path);
+            }
+        }
+    }
+
+    found
+}
+
+/// Compiles every discovered shim `.c` file to LLVM bitcode with clang, then merges
+/// them into a single module so the verification pipeline can link the shim's
+/// intrinsics straight into the Boogie translation, regardless of how many
+/// companion `.c` files the shim has grown.
+fn emit_bitcode(c_files: &[&PathBuf]) {
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set by cargo"));
+    let clang = env::var("CLANG").unwrap_or_else(|_| "clang".to_string());
+
+    let mut bc_files = Vec::new();
+    for c_file in c_files {
+        let bc_path = out_dir.join(c_file.file_stem().unwrap()).with_extension("bc");
+        let status = Command::new(&clang)
+            .arg("-emit-llvm")
+            .arg("-c")
+            .arg("-DCARGO_BUILD")
+            .arg("-Isrc")
+            .arg(c_file)
+            .arg("-o")
+            .arg(&bc_path)
+            .status()
+            .unwrap_or_else(|e| panic!("failed to run {}: {}", clang, e));
+        assert!(
+            status.success(),
+            "{} -emit-llvm failed on {}",
+            clang,
+            c_file.display()
+        );
+        bc_files.push(bc_path);
+    }
+
+    // Not `smack-rust.bc`: that is the per-file output for `smack-rust.c` above, and
+    // copying or linking it onto itself would truncate it.
+    let runtime_bc = out_dir.join("smack-runtime.bc");
+    if bc_files.len() == 1 {
+        fs::copy(&bc_files[0], &runtime_bc).expect("failed to copy shim bitcode");
+    } else {
+        let llvm_link = env::var("LLVM_LINK").unwrap_or_else(|_| "llvm-link".to_string());
+        let status = Command::new(&llvm_link)
+            .args(&bc_files)
+            .arg("-o")
+            .arg(&runtime_bc)
+            .status()
+            .unwrap_or_else(|e| panic!("failed to run {}: {}", llvm_link, e));
+        assert!(
+            status.success(),
+            "{} failed to merge shim bitcode",
+            llvm_link
+        );
+    }
+
+    println!("cargo:rustc-env=SMACK_RUNTIME_BC={}", runtime_bc.display());
+    println!("cargo:rustc-cfg=smack_verify");
 }
 
+fn main() {
+    let target = env::var("TARGET").unwrap_or_else(|_| "x86_64-unknown-linux-gnu".to_string());
+    let triple = Triple::parse(&target);
+    let ptr_width = triple.ptr_width();
+
+    let sources = find_shim_sources(Path::new("src"));
+    for source in &sources {
+        println!("cargo:rerun-if-changed={}", source.display());
+    }
+    let c_files: Vec<&PathBuf> = sources
+        .iter()
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("c"))
+        .collect();
+
+    if env::var_os("SMACK_EMIT_BITCODE").is_some() {
+        emit_bitcode(&c_files);
+        // Only the bitcode path feeds LLVM/Clang support libraries into the final
+        // link; the plain `cc::Build` archive below is pure C and needs neither.
+        println!("cargo:rustc-link-lib=dylib={}", triple.cxx_stdlib());
+    } else {
+        let mut build = cc::Build::new();
+        build
+            .define("CARGO_BUILD", None)
+            .define("SMACK_PTR_WIDTH", ptr_width)
+            .include("src");
+        if let Some((compiler, archiver)) = triple.cxx_driver() {
+            build.compiler(compiler).archiver(archiver);
+        }
+        for c_file in &c_files {
+            build.file(c_file);
+        }
+        build.compile("libsmack.a");
+    }
+
+    println!("cargo:rustc-link-search=native=bin/{}", triple.link_dir());
+    println!(
+        "cargo:rustc-link-search=native=lib/{}/{}",
+        triple.os(),
+        triple.arch()
+    );
+    println!("cargo:rustc-cfg=smack_ptr_width=\"{}\"", ptr_width);
+
+    println!("cargo:rerun-if-env-changed=TARGET");
+    println!("cargo:rerun-if-env-changed=SMACK_EMIT_BITCODE");
+    println!("cargo:rerun-if-env-changed=SMACK_CXX_STDLIB");
+}
@@ -0,0 +1,4 @@
+//! Rust-side half of the SMACK C shim compiled from `src/smack-rust.c` by `build.rs`.
+//!
+//! The C shim provides the `__VERIFIER_nondet_*` / assert / assume intrinsics the
+//! Boogie translation needs; this crate is the entry point cargo links it through.